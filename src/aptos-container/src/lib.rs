@@ -1,5 +1,13 @@
 extern crate testcontainers;
 
+mod pool;
+mod scheduler;
+mod topology;
+
+pub use pool::{AptosContainerPool, PooledContainer};
+pub use scheduler::{DeployJob, DeployResult, DeployScheduler, DeployStatus};
+pub use topology::{ComposeSpec, ServiceSpec, Topology};
+
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::path::{Path, PathBuf};
@@ -8,8 +16,11 @@ use std::time::Duration;
 use std::{fs, path};
 
 use anyhow::{ensure, Error, Result};
+use async_stream::try_stream;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use bytes::Bytes;
+use futures::Stream;
 use log::debug;
 use testcontainers::core::{ExecCommand, IntoContainerPort, WaitFor};
 use testcontainers::runners::AsyncRunner;
@@ -21,8 +32,8 @@ use tokio::time::Instant;
 
 use container_interface::config::EnvConfig;
 use container_interface::error::MovementTestContainerError::{CommandFailed, DockerExecFailed};
-use container_interface::utils::{generate_random_string, get_files};
-use container_interface::TestContainer;
+use container_interface::utils::{generate_random_string, get_files, CopyOptions};
+use container_interface::{LogChunk, TestContainer};
 
 pub struct AptosContainer {
     node_url: String,
@@ -32,6 +43,7 @@ pub struct AptosContainer {
     override_accounts: Option<Vec<String>>,
     container: ContainerAsync<GenericImage>,
     contract_path: String,
+    copy_options: CopyOptions,
     contracts: Mutex<HashSet<String>>,
     accounts: RwLock<Vec<String>>,
     accounts_channel_rx: Mutex<Option<Receiver<String>>>,
@@ -40,10 +52,11 @@ pub struct AptosContainer {
 
 const APTOS_IMAGE: &str = "sotazklabs/aptos-tools";
 const APTOS_IMAGE_TAG: &str = "mainnet";
-const FILTER_PATTERN: &str = r"^(?:\.git|target\/|.idea|Cargo.lock|build\/|.aptos\/)";
 const ACCOUNTS_ENV: &str = "ACCOUNTS";
-const CONTENT_MAX_CHARS: usize = 120000;
+const VM_STATUS_SUCCESS: &str = "Executed successfully";
 const MOVE_TOML: &[u8] = &[0];
+// Upper bound on a contract archive transferred via a single heredoc'd exec.
+const MAX_ARCHIVE_BYTES: usize = 16 * 1024 * 1024;
 
 impl TestContainer for AptosContainer {
     async fn get_node_url(&self) -> String {
@@ -73,6 +86,52 @@ impl TestContainer for AptosContainer {
         Ok((stdout, stderr))
     }
 
+    async fn run_command_streaming(
+        &self,
+        command: &str,
+    ) -> Result<impl Stream<Item = Result<LogChunk>>> {
+        let result = self
+            .container
+            .exec(ExecCommand::new(vec!["/bin/sh", "-c", command]))
+            .await?;
+
+        // `ExecResult::stdout`/`stderr` each need `&mut self`, so only one can
+        // be read at a time; the generator below owns `result` and re-borrows
+        // it fresh for every read instead of holding two readers alive
+        // simultaneously. Each frame is yielded as soon as it's read, so a
+        // caller sees output as the command produces it instead of waiting for
+        // the whole exec to finish and buffering it all in memory. Per-channel
+        // order is preserved, but the two channels are still emitted
+        // stdout-then-stderr rather than interleaved by arrival time, since
+        // only one channel can be read at once. The exit code is checked only
+        // once both channels hit EOF, so surfacing a non-zero exit (as
+        // `run_command` does) doesn't block the first frame on completion.
+        Ok(try_stream! {
+            let mut result = result;
+            const FRAME_SIZE: usize = 8192;
+            let mut buf = vec![0u8; FRAME_SIZE];
+
+            loop {
+                let n = result.stdout().read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                yield LogChunk::Stdout(Bytes::copy_from_slice(&buf[..n]));
+            }
+            loop {
+                let n = result.stderr().read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                yield LogChunk::Stderr(Bytes::copy_from_slice(&buf[..n]));
+            }
+
+            if let Some(code) = result.exit_code().await?.filter(|code| *code != 0) {
+                Err(Error::new(DockerExecFailed(code)))?;
+            }
+        })
+    }
+
     async fn lazy_init_accounts(&self) -> Result<()> {
         if self.override_accounts.is_some() {
             return Ok(());
@@ -110,37 +169,32 @@ impl TestContainer for AptosContainer {
     }
 
     async fn copy_contracts(&self, local_dir: impl AsRef<Path>) -> Result<PathBuf> {
-        let contract_path =
-            Path::new(&self.contract_path).join(generate_random_string(6));
-        let contract_path_str = contract_path.to_str().unwrap();
+        self.copy_contracts_with_options(local_dir, &self.copy_options)
+            .await
+    }
 
-        let command = format!("rm -rf {}", contract_path_str);
-        let (_, stderr) = self.run_command(&command).await?;
-        ensure!(stderr.is_empty(), CommandFailed { command, stderr });
+    async fn copy_artifacts(&self, container_subdir: &str, local_dest: &Path) -> Result<PathBuf> {
+        // This is how callers retrieve the `build/` output of `aptos move
+        // publish` that `get_files` skips. `testcontainers` doesn't expose the
+        // Docker `GET .../archive` endpoint, so the archive is base64-wrapped
+        // over `run_command`'s stdout (to survive the text-only exec channel)
+        // and buffered fully in memory rather than streamed. The size check
+        // below runs on that base64 text after it has been read in full, and
+        // rejects an oversized archive before it is decoded and unpacked.
+        let command = format!("tar -c -C '{}' . | base64", container_subdir);
+        let (stdout, stderr) = self.run_command(&command).await?;
+        ensure!(!stdout.is_empty(), CommandFailed { command, stderr });
+        ensure!(
+            stdout.len() <= MAX_ARCHIVE_BYTES,
+            "artifact archive from {} is over the {} byte limit for a single exec transfer",
+            container_subdir,
+            MAX_ARCHIVE_BYTES
+        );
 
-        let local_dir_str = local_dir.as_ref().to_str().unwrap();
-        for entry in get_files(local_dir_str, FILTER_PATTERN) {
-            let source_path = entry.path();
-            let relative_path = source_path.strip_prefix(local_dir_str)?;
-            let dest_path = contract_path.join(relative_path);
-            let content = fs::read(source_path)?;
-            let encoded_content = BASE64_STANDARD.encode(&content);
-            for chunk in encoded_content
-                .chars()
-                .collect::<Vec<char>>()
-                .chunks(CONTENT_MAX_CHARS)
-            {
-                let command = format!(
-                    "mkdir -p \"$(dirname '{}')\" && (echo '{}' | base64 --decode >> '{}')",
-                    dest_path.to_str().unwrap(),
-                    chunk.iter().collect::<String>(),
-                    dest_path.to_str().unwrap()
-                );
-                let (_, stderr) = self.run_command(&command).await?;
-                ensure!(stderr.is_empty(), CommandFailed { command, stderr });
-            }
-        }
-        Ok(contract_path)
+        let archive = BASE64_STANDARD.decode(stdout.split_whitespace().collect::<String>())?;
+        fs::create_dir_all(local_dest)?;
+        tar::Archive::new(archive.as_slice()).unpack(local_dest)?;
+        Ok(local_dest.to_path_buf())
     }
 
     async fn run(
@@ -177,8 +231,53 @@ impl TestContainer for AptosContainer {
     }
 
     async fn upload_contracts(&self, local_dir: &str, private_key: &str, named_addresses: &HashMap<String, String>, sub_packages: Option<Vec<&str>>, override_contract: bool) -> Result<()> {
+        let report = self
+            .try_upload_contracts(local_dir, private_key, named_addresses, sub_packages, override_contract)
+            .await?;
+        ensure!(
+            report.vm_status.as_deref() == Some(VM_STATUS_SUCCESS),
+            CommandFailed {
+                command: format!("aptos move publish ({})", local_dir),
+                stderr: report.stderr,
+            }
+        );
+        Ok(())
+    }
+}
+
+// The per-job outcome of a publish attempt, so callers (the deploy scheduler)
+// can report a package's real status without aborting the whole batch.
+pub struct PublishReport {
+    pub vm_status: Option<String>,
+    pub stderr: String,
+}
+
+// Pull the vm_status value out of an `aptos move publish` JSON result.
+fn extract_vm_status(stdout: &str) -> Option<String> {
+    let marker = r#""vm_status": ""#;
+    let start = stdout.find(marker)? + marker.len();
+    let rest = &stdout[start..];
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+impl AptosContainer {
+    // Deploy a package and report its outcome instead of erroring on a failed
+    // vm_status. Infrastructure failures (copying files, writing Move.toml)
+    // still surface as Err.
+    pub async fn try_upload_contracts(
+        &self,
+        local_dir: &str,
+        private_key: &str,
+        named_addresses: &HashMap<String, String>,
+        sub_packages: Option<Vec<&str>>,
+        override_contract: bool,
+    ) -> Result<PublishReport> {
         if !self.deploy_contract {
-            return Ok(());
+            return Ok(PublishReport {
+                vm_status: Some(VM_STATUS_SUCCESS.to_string()),
+                stderr: String::new(),
+            });
         }
 
         let absolute = path::absolute(local_dir)?;
@@ -187,7 +286,10 @@ impl TestContainer for AptosContainer {
 
         let mut inserted_contracts = self.contracts.lock().await;
         if !override_contract && inserted_contracts.contains(&contract_key) {
-            return Ok(());
+            return Ok(PublishReport {
+                vm_status: Some(VM_STATUS_SUCCESS.to_string()),
+                stderr: String::new(),
+            });
         }
         let now = Instant::now();
         let contract_path = self.copy_contracts(local_dir).await?;
@@ -214,46 +316,132 @@ impl TestContainer for AptosContainer {
             .reduce(|acc, cur| format!("{},{}", acc, cur))
             .map(|named_addresses| format!("--named-addresses {}", named_addresses))
             .unwrap_or("".to_string());
-        match sub_packages {
-            None => {
-                let command = format!(
-                    "cd {} && aptos move publish --skip-fetch-latest-git-deps --private-key {} --assume-yes {} --url {} --included-artifacts none",
-                    contract_path_str, private_key, named_address_params, self.inner_url
-                );
-                let (stdout, stderr) = self.run_command(&command).await?;
-                ensure!(
-                    stdout.contains(r#""vm_status": "Executed successfully""#),
-                    CommandFailed {
-                        command,
-                        stderr: format!("stdout: {} \n\n stderr: {}", stdout, stderr)
-                    }
-                );
-            }
-            Some(sub_packages) => {
-                for sub_package in sub_packages {
-                    let command = format!(
-                        "cd {}/{} && aptos move publish --skip-fetch-latest-git-deps --private-key {} --assume-yes {} --url {} --included-artifacts none",
-                        contract_path_str, sub_package, private_key, named_address_params, self.inner_url
-                    );
-                    let (stdout, stderr) = self.run_command(&command).await?;
-                    ensure!(
-                        stdout.contains(r#""vm_status": "Executed successfully""#),
-                        CommandFailed {
-                            command,
-                            stderr: format!("stdout: {} \n\n stderr: {}", stdout, stderr)
-                        }
-                    );
-                }
+
+        // One publish per (sub-)package; the package dirs to deploy in.
+        let package_dirs = match &sub_packages {
+            None => vec![contract_path_str.to_string()],
+            Some(sub_packages) => sub_packages
+                .iter()
+                .map(|sub_package| format!("{}/{}", contract_path_str, sub_package))
+                .collect(),
+        };
+
+        for dir in package_dirs {
+            let command = format!(
+                "cd {} && aptos move publish --skip-fetch-latest-git-deps --private-key {} --assume-yes {} --url {} --included-artifacts none",
+                dir, private_key, named_address_params, self.inner_url
+            );
+            let (stdout, stderr) = self.run_command(&command).await?;
+            let vm_status = extract_vm_status(&stdout);
+            if vm_status.as_deref() != Some(VM_STATUS_SUCCESS) {
+                // Report the failure without aborting: carry the real output back.
+                return Ok(PublishReport {
+                    vm_status,
+                    stderr: format!("stdout: {} \n\n stderr: {}", stdout, stderr),
+                });
             }
         }
 
         inserted_contracts.insert(contract_key);
-        Ok(())
+        Ok(PublishReport {
+            vm_status: Some(VM_STATUS_SUCCESS.to_string()),
+            stderr: String::new(),
+        })
     }
-}
 
-impl AptosContainer {
-    async fn init() -> Result<Self> {
+    // Copy a contract directory in using explicit CopyOptions, overriding the
+    // container's default filtering. `copy_contracts` delegates here.
+    pub async fn copy_contracts_with_options(
+        &self,
+        local_dir: impl AsRef<Path>,
+        options: &CopyOptions,
+    ) -> Result<PathBuf> {
+        let contract_path =
+            Path::new(&self.contract_path).join(generate_random_string(6));
+        let contract_path_str = contract_path.to_str().unwrap();
+
+        let command = format!("rm -rf {}", contract_path_str);
+        let (_, stderr) = self.run_command(&command).await?;
+        ensure!(stderr.is_empty(), CommandFailed { command, stderr });
+
+        let local_dir_str = local_dir.as_ref().to_str().unwrap();
+
+        // Pack every filtered file into a single in-memory tar archive keyed by
+        // its path relative to `local_dir`, then transfer and extract it inside
+        // the container in one exec below. This replaces the previous per-file
+        // `echo '...' | base64 --decode` loop with a single archive and a
+        // single round trip, rather than one (or even one-per-chunk) exec per
+        // file.
+        let mut builder = tar::Builder::new(Vec::new());
+        for entry in get_files(local_dir_str, options)? {
+            let source_path = entry.path();
+            let relative_path = source_path.strip_prefix(local_dir_str)?;
+            builder.append_path_with_name(source_path, relative_path)?;
+        }
+        let archive = builder.into_inner()?;
+        ensure!(
+            archive.len() <= MAX_ARCHIVE_BYTES,
+            "contract archive for {} is {} bytes, over the {} byte limit for a single exec transfer; \
+             narrow `copy_options` (e.g. disable `include_build`) to shrink it",
+            local_dir_str,
+            archive.len(),
+            MAX_ARCHIVE_BYTES
+        );
+        let encoded = BASE64_STANDARD.encode(&archive);
+
+        let command = format!("mkdir -p '{}'", contract_path_str);
+        let (_, stderr) = self.run_command(&command).await?;
+        ensure!(stderr.is_empty(), CommandFailed { command, stderr });
+
+        // `testcontainers`' exec doesn't expose the raw Docker `PUT
+        // .../archive` endpoint or a way to pipe bytes into an exec's stdin, so
+        // the archive is embedded as a heredoc in the `/bin/sh -c` command
+        // instead: one round trip rather than a per-chunk loop. The size check
+        // above keeps that command bounded rather than letting it grow with
+        // whatever `copy_options` happens to select.
+        let command = format!(
+            "base64 --decode <<'MOVEMENT_TAR_EOF' | tar -x -C '{}'\n{}\nMOVEMENT_TAR_EOF",
+            contract_path_str, encoded
+        );
+        let (_, stderr) = self.run_command(&command).await?;
+        ensure!(stderr.is_empty(), CommandFailed { command, stderr });
+
+        Ok(contract_path)
+    }
+
+    // Number of accounts the node handed out; fallback concurrency bound
+    // where there's no lease channel to wait on. 0 until accounts are init'd.
+    pub(crate) async fn account_count(&self) -> usize {
+        match &self.override_accounts {
+            Some(accounts) => accounts.len(),
+            None => self.accounts.read().await.len(),
+        }
+    }
+
+    // Check out one account, blocking until one becomes available. Returns
+    // None when accounts are caller-supplied (override_accounts set): there's
+    // no channel to lease from in that case.
+    pub(crate) async fn lease_account(&self) -> Option<String> {
+        if self.override_accounts.is_some() {
+            return None;
+        }
+        self.accounts_channel_rx
+            .lock()
+            .await
+            .as_mut()
+            .unwrap()
+            .recv()
+            .await
+    }
+
+    // Return an account checked out via lease_account to the pool.
+    pub(crate) async fn return_account(&self, account: String) {
+        if let Some(tx) = self.accounts_channel_tx.read().await.as_ref() {
+            let _ = tx.send(account).await;
+        }
+    }
+
+    pub(crate) async fn init() -> Result<Self> {
         let config = EnvConfig::new();
         let enable_node = config.enable_node.unwrap_or(true);
 
@@ -308,6 +496,7 @@ impl AptosContainer {
             container,
             override_accounts,
             contract_path: "/contract".to_string(),
+            copy_options: CopyOptions::default(),
             contracts: Default::default(),
             accounts: Default::default(),
             accounts_channel_rx: Default::default(),
@@ -0,0 +1,103 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use log::debug;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use container_interface::TestContainer;
+
+use crate::AptosContainer;
+
+// Checkout/return pool of warmed-up containers, since starting a localnet
+// node takes ~10s and a test suite otherwise pays that per test.
+pub struct AptosContainerPool {
+    idle: Mutex<Vec<AptosContainer>>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl AptosContainerPool {
+    // Hands out at most `max_size` containers at a time. Call `init` to warm
+    // it up eagerly.
+    pub fn new(max_size: usize) -> Arc<Self> {
+        Arc::new(Self {
+            idle: Mutex::new(Vec::new()),
+            semaphore: Arc::new(Semaphore::new(max_size)),
+        })
+    }
+
+    // Eagerly starts up to `size` containers. Safe to call more than once:
+    // tops up to `size` idle containers rather than adding `size` more.
+    pub async fn init(self: &Arc<Self>, size: usize) -> Result<()> {
+        let idle_len = self.idle.lock().unwrap().len();
+        let target = size.saturating_sub(idle_len).min(self.semaphore.available_permits());
+        let mut containers = Vec::with_capacity(target);
+        for _ in 0..target {
+            containers.push(AptosContainer::init().await?);
+        }
+        self.idle.lock().unwrap().extend(containers);
+        Ok(())
+    }
+
+    // Checks out an idle container, awaiting a free slot if the pool is at
+    // capacity. Starts one on demand if none are idle. Unhealthy containers
+    // are discarded and replaced.
+    pub async fn acquire(self: &Arc<Self>) -> Result<PooledContainer> {
+        let permit = self.semaphore.clone().acquire_owned().await?;
+        loop {
+            let candidate = self.idle.lock().unwrap().pop();
+            let container = match candidate {
+                Some(container) => container,
+                None => AptosContainer::init().await?,
+            };
+
+            if is_healthy(&container).await {
+                return Ok(PooledContainer {
+                    container: Some(container),
+                    pool: self.clone(),
+                    _permit: permit,
+                });
+            }
+            debug!("discarding unhealthy pooled container");
+        }
+    }
+
+    fn checkin(&self, container: AptosContainer) {
+        self.idle.lock().unwrap().push(container);
+    }
+}
+
+// Cheap liveness probe: resolve the node URL and run a no-op exec.
+async fn is_healthy(container: &AptosContainer) -> bool {
+    let _ = container.get_node_url().await;
+    container.run_command("true").await.is_ok()
+}
+
+// A container checked out of an AptosContainerPool; returns itself on drop.
+pub struct PooledContainer {
+    container: Option<AptosContainer>,
+    pool: Arc<AptosContainerPool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledContainer {
+    type Target = AptosContainer;
+
+    fn deref(&self) -> &Self::Target {
+        self.container.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledContainer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.container.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledContainer {
+    fn drop(&mut self) {
+        if let Some(container) = self.container.take() {
+            self.pool.checkin(container);
+        }
+    }
+}
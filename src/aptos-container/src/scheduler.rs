@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+use log::debug;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+use container_interface::TestContainer;
+
+use crate::AptosContainer;
+
+// Layer jobs into dependency-ordered waves; jobs stuck in a cycle or
+// depending on an unknown name come back in the second element instead of
+// looping forever.
+fn plan_waves(deps: &HashMap<String, Vec<String>>) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut done: HashSet<String> = HashSet::new();
+    let mut waves: Vec<Vec<String>> = Vec::new();
+
+    loop {
+        let mut wave: Vec<String> = deps
+            .iter()
+            .filter(|(name, _)| !done.contains(*name))
+            .filter(|(_, requires)| requires.iter().all(|d| done.contains(d)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if wave.is_empty() {
+            break;
+        }
+        wave.sort();
+        for name in &wave {
+            done.insert(name.clone());
+        }
+        waves.push(wave);
+    }
+
+    let mut unresolved: Vec<String> = deps
+        .keys()
+        .filter(|name| !done.contains(*name))
+        .cloned()
+        .collect();
+    unresolved.sort();
+    (waves, unresolved)
+}
+
+// A single package deployment to run through the DeployScheduler; runs once
+// every name in `depends_on` has deployed successfully.
+pub struct DeployJob {
+    pub name: String,
+    pub local_dir: String,
+    pub private_key: String,
+    pub named_addresses: HashMap<String, String>,
+    pub sub_packages: Option<Vec<String>>,
+    pub override_contract: bool,
+    pub depends_on: Vec<String>,
+}
+
+impl DeployJob {
+    pub fn new(name: impl Into<String>, local_dir: impl Into<String>, private_key: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            local_dir: local_dir.into(),
+            private_key: private_key.into(),
+            named_addresses: HashMap::new(),
+            sub_packages: None,
+            override_contract: false,
+            depends_on: Vec::new(),
+        }
+    }
+}
+
+// The outcome of a single deploy job: whether it executed, and how long it took.
+pub struct DeployResult {
+    pub name: String,
+    pub status: DeployStatus,
+    pub elapsed: std::time::Duration,
+}
+
+pub enum DeployStatus {
+    Succeeded { vm_status: String },
+    Failed { stderr: String },
+}
+
+impl DeployStatus {
+    pub fn is_success(&self) -> bool {
+        matches!(self, DeployStatus::Succeeded { .. })
+    }
+}
+
+// Deploys a set of independent packages concurrently: resolves a dependency
+// order, bounds concurrency by real account availability, and reports a
+// result per job instead of aborting the batch on the first failure.
+pub struct DeployScheduler<'a> {
+    container: &'a AptosContainer,
+}
+
+impl<'a> DeployScheduler<'a> {
+    pub fn new(container: &'a AptosContainer) -> Self {
+        Self { container }
+    }
+
+    pub async fn run(&self, jobs: Vec<DeployJob>) -> Result<Vec<DeployResult>> {
+        // Jobs lease a real account below; this semaphore is only the fallback
+        // for when there's no channel to lease from (`override_accounts` set).
+        self.container.lazy_init_accounts().await?;
+        let concurrency = self.container.account_count().await.max(1);
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        debug!("deploy scheduler running {} job(s), concurrency {}", jobs.len(), concurrency);
+
+        let jobs: HashMap<String, DeployJob> =
+            jobs.into_iter().map(|j| (j.name.clone(), j)).collect();
+
+        // Layer the jobs into dependency-ordered waves up front. Anything left
+        // over forms a cycle or depends on an unknown name.
+        let deps: HashMap<String, Vec<String>> = jobs
+            .iter()
+            .map(|(name, job)| (name.clone(), job.depends_on.clone()))
+            .collect();
+        let (waves, unresolved) = plan_waves(&deps);
+
+        let mut succeeded: HashSet<String> = HashSet::new();
+        let mut results: Vec<DeployResult> = Vec::with_capacity(jobs.len());
+
+        // Each wave's jobs are independent and run concurrently, bounded by
+        // real account availability; the next wave starts once the current one
+        // finishes.
+        for wave in waves {
+            let succeeded_ref = &succeeded;
+            let wave_results = futures::future::join_all(wave.iter().map(|name| {
+                let job = &jobs[name];
+                let semaphore = semaphore.clone();
+                async move {
+                    // Prefer a real leased account; fall back to the static
+                    // semaphore only when there's no channel to lease from.
+                    let leased = self.container.lease_account().await;
+                    let _permit = if leased.is_none() {
+                        Some(semaphore.acquire().await.unwrap())
+                    } else {
+                        None
+                    };
+                    let result = self.deploy_one(job, succeeded_ref).await;
+                    if let Some(account) = leased {
+                        self.container.return_account(account).await;
+                    }
+                    result
+                }
+            }))
+            .await;
+
+            for result in wave_results {
+                if result.status.is_success() {
+                    succeeded.insert(result.name.clone());
+                }
+                results.push(result);
+            }
+        }
+
+        // Report unresolvable jobs as failed rather than silently dropping them.
+        for name in unresolved {
+            results.push(DeployResult {
+                name,
+                status: DeployStatus::Failed {
+                    stderr: "unresolved dependency or dependency cycle".to_string(),
+                },
+                elapsed: std::time::Duration::ZERO,
+            });
+        }
+
+        Ok(results)
+    }
+
+    async fn deploy_one(&self, job: &DeployJob, succeeded: &HashSet<String>) -> DeployResult {
+        let now = Instant::now();
+
+        if let Some(failed_dep) = job.depends_on.iter().find(|d| !succeeded.contains(*d)) {
+            return DeployResult {
+                name: job.name.clone(),
+                status: DeployStatus::Failed {
+                    stderr: format!("skipped: dependency {} did not succeed", failed_dep),
+                },
+                elapsed: now.elapsed(),
+            };
+        }
+
+        let sub_packages = job
+            .sub_packages
+            .as_ref()
+            .map(|packages| packages.iter().map(String::as_str).collect::<Vec<_>>());
+        let status = match self
+            .container
+            .try_upload_contracts(
+                &job.local_dir,
+                &job.private_key,
+                &job.named_addresses,
+                sub_packages,
+                job.override_contract,
+            )
+            .await
+        {
+            // The publish ran: carry its real vm_status (on success) or captured
+            // stderr (on failure) into the per-job report.
+            Ok(report) => match report.vm_status {
+                Some(vm_status) if report.stderr.is_empty() => {
+                    DeployStatus::Succeeded { vm_status }
+                }
+                _ => DeployStatus::Failed {
+                    stderr: report.stderr,
+                },
+            },
+            // An infrastructure failure (copying files, writing Move.toml, or the
+            // publish exec itself failing) that left no vm_status to report.
+            Err(err) => DeployStatus::Failed {
+                stderr: err.to_string(),
+            },
+        };
+
+        DeployResult {
+            name: job.name.clone(),
+            status,
+            elapsed: now.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deps(pairs: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(name, requires)| {
+                (name.to_string(), requires.iter().map(|s| s.to_string()).collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn independent_jobs_form_a_single_wave() {
+        let (waves, unresolved) = plan_waves(&deps(&[("a", &[]), ("b", &[]), ("c", &[])]));
+        assert_eq!(waves, vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]]);
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn dependencies_are_layered_into_ordered_waves() {
+        let (waves, unresolved) =
+            plan_waves(&deps(&[("a", &[]), ("b", &["a"]), ("c", &["b"])]));
+        assert_eq!(
+            waves,
+            vec![vec!["a".to_string()], vec!["b".to_string()], vec!["c".to_string()]]
+        );
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn cycles_are_reported_as_unresolved() {
+        let (waves, unresolved) = plan_waves(&deps(&[("a", &["b"]), ("b", &["a"])]));
+        assert!(waves.is_empty());
+        assert_eq!(unresolved, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn unknown_dependency_is_reported_as_unresolved() {
+        let (waves, unresolved) = plan_waves(&deps(&[("a", &[]), ("b", &["ghost"])]));
+        assert_eq!(waves, vec![vec!["a".to_string()]]);
+        assert_eq!(unresolved, vec!["b".to_string()]);
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, ensure, Result};
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+use container_interface::utils::generate_random_string;
+
+// One service in a ComposeSpec: an image, environment, exposed ports, a
+// readiness condition, and the services it must start after.
+pub struct ServiceSpec {
+    name: String,
+    image: String,
+    tag: String,
+    env: HashMap<String, String>,
+    exposed_ports: Vec<u16>,
+    wait_for: Vec<WaitFor>,
+    depends_on: Vec<String>,
+}
+
+impl ServiceSpec {
+    pub fn new(
+        name: impl Into<String>,
+        image: impl Into<String>,
+        tag: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            image: image.into(),
+            tag: tag.into(),
+            env: HashMap::new(),
+            exposed_ports: Vec::new(),
+            wait_for: Vec::new(),
+            depends_on: Vec::new(),
+        }
+    }
+
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_exposed_port(mut self, port: u16) -> Self {
+        self.exposed_ports.push(port);
+        self
+    }
+
+    pub fn with_wait_for(mut self, wait_for: WaitFor) -> Self {
+        self.wait_for.push(wait_for);
+        self
+    }
+
+    pub fn depends_on(mut self, service: impl Into<String>) -> Self {
+        self.depends_on.push(service.into());
+        self
+    }
+}
+
+// A builder for a multi-service topology brought up on a shared network.
+#[derive(Default)]
+pub struct ComposeSpec {
+    services: Vec<ServiceSpec>,
+}
+
+impl ComposeSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_service(mut self, service: ServiceSpec) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    // Starts every service on a common network in `depends_on` order, so a
+    // service only starts once everything it depends on reports ready.
+    pub async fn up(self) -> Result<Topology> {
+        let order = resolve_order(&self.services)?;
+        let by_name: HashMap<&str, &ServiceSpec> =
+            self.services.iter().map(|s| (s.name.as_str(), s)).collect();
+
+        let network = format!("movement-{}", generate_random_string(8));
+        let mut services = HashMap::new();
+        for name in order {
+            let spec = by_name[name.as_str()];
+            let mut image = GenericImage::new(&spec.image, &spec.tag);
+            for port in &spec.exposed_ports {
+                image = image.with_exposed_port(port.tcp());
+            }
+            for wait_for in &spec.wait_for {
+                image = image.with_wait_for(wait_for.clone());
+            }
+            let mut image = image.with_network(&network);
+            for (key, value) in &spec.env {
+                image = image.with_env_var(key, value);
+            }
+            let container = image.start().await?;
+            services.insert(spec.name.clone(), container);
+        }
+
+        Ok(Topology { network, services })
+    }
+}
+
+// A running multi-service stack, with per-service resolved host URLs.
+pub struct Topology {
+    network: String,
+    services: HashMap<String, ContainerAsync<GenericImage>>,
+}
+
+impl Topology {
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    pub fn service(&self, name: &str) -> Option<&ContainerAsync<GenericImage>> {
+        self.services.get(name)
+    }
+
+    pub async fn host_url(&self, service: &str, port: u16) -> Result<String> {
+        let container = self
+            .services
+            .get(service)
+            .ok_or_else(|| anyhow::anyhow!("unknown service: {}", service))?;
+        Ok(format!(
+            "http://{}:{}",
+            container.get_host().await?,
+            container.get_host_port_ipv4(port).await?
+        ))
+    }
+}
+
+// Topologically sort services by `depends_on` so dependencies come first.
+// Errors on an unknown dependency or a dependency cycle.
+fn resolve_order(services: &[ServiceSpec]) -> Result<Vec<String>> {
+    let names: HashSet<&str> = services.iter().map(|s| s.name.as_str()).collect();
+    let mut remaining: Vec<&ServiceSpec> = services.iter().collect();
+    let mut resolved: Vec<String> = Vec::with_capacity(services.len());
+    let mut done: HashSet<String> = HashSet::new();
+
+    for service in services {
+        for dep in &service.depends_on {
+            ensure!(
+                names.contains(dep.as_str()),
+                "service {} depends on unknown service {}",
+                service.name,
+                dep
+            );
+        }
+    }
+
+    while !remaining.is_empty() {
+        let ready_idx = remaining
+            .iter()
+            .position(|s| s.depends_on.iter().all(|d| done.contains(d)));
+        match ready_idx {
+            Some(idx) => {
+                let service = remaining.remove(idx);
+                done.insert(service.name.clone());
+                resolved.push(service.name.clone());
+            }
+            None => bail!("dependency cycle detected in compose spec"),
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, depends_on: &[&str]) -> ServiceSpec {
+        let mut service = ServiceSpec::new(name, "image", "tag");
+        for dep in depends_on {
+            service = service.depends_on(*dep);
+        }
+        service
+    }
+
+    fn position(order: &[String], name: &str) -> usize {
+        order.iter().position(|n| n == name).unwrap()
+    }
+
+    #[test]
+    fn dependencies_precede_dependents() {
+        let services = vec![
+            spec("node", &[]),
+            spec("indexer", &["node", "postgres"]),
+            spec("postgres", &[]),
+            spec("faucet", &["node"]),
+        ];
+        let order = resolve_order(&services).unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(position(&order, "node") < position(&order, "indexer"));
+        assert!(position(&order, "postgres") < position(&order, "indexer"));
+        assert!(position(&order, "node") < position(&order, "faucet"));
+    }
+
+    #[test]
+    fn unknown_dependency_errors() {
+        let services = vec![spec("node", &[]), spec("indexer", &["ghost"])];
+        let err = resolve_order(&services).unwrap_err();
+        assert!(err.to_string().contains("unknown service"));
+    }
+
+    #[test]
+    fn dependency_cycle_errors() {
+        let services = vec![spec("a", &["b"]), spec("b", &["a"])];
+        let err = resolve_order(&services).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+}
@@ -8,12 +8,29 @@ use std::path::{Path, PathBuf};
 use std::pin::Pin;
 
 use anyhow::Result;
+use bytes::Bytes;
+use futures::Stream;
+
+// A demultiplexed frame of container output, yielded incrementally by
+// `run_command_streaming` instead of buffering the whole command's output.
+#[derive(Debug, Clone)]
+pub enum LogChunk {
+    Stdout(Bytes),
+    Stderr(Bytes),
+}
 
 pub trait TestContainer {
     async fn get_node_url(&self) -> String;
     async fn run_command(&self, command: &str) -> Result<(String, String)>;
+    // Item is a Result: a read or the exec's exit-code check can fail after
+    // earlier frames were already yielded.
+    async fn run_command_streaming(
+        &self,
+        command: &str,
+    ) -> Result<impl Stream<Item = Result<LogChunk>>>;
     async fn lazy_init_accounts(&self) -> Result<()>;
     async fn copy_contracts(&self, local_dir: impl AsRef<Path>) -> Result<PathBuf>;
+    async fn copy_artifacts(&self, container_subdir: &str, local_dest: &Path) -> Result<PathBuf>;
     async fn run(
         &self,
         number_of_accounts: usize,
@@ -1,3 +1,4 @@
+use log::{debug, warn};
 use rand::distr::Alphanumeric;
 use rand::Rng;
 use regex::Regex;
@@ -14,32 +15,235 @@ pub fn generate_random_string(length: usize) -> String {
     random_string
 }
 
-pub fn get_files(local_dir: &str, filter_pattern: &str) -> Vec<DirEntry> {
-    WalkDir::new(local_dir)
+// VCS/editor metadata and generated output a Move workspace never needs uploaded.
+const DEFAULT_EXCLUDES: &[&str] = &[
+    r"^\.git",
+    r"^target/",
+    r"^\.idea",
+    r"^Cargo\.lock$",
+    r"^\.aptos/",
+];
+
+// Controls which files get_files collects.
+pub struct CopyOptions {
+    // Skip files strictly larger than this many bytes. None means no limit.
+    pub max_file_size: Option<u64>,
+    // Keep a path only if it matches one of these. Empty keeps everything not excluded.
+    pub include: Vec<String>,
+    // Skip a path matching any of these; takes precedence over `include`.
+    pub exclude: Vec<String>,
+    pub follow_symlinks: bool,
+    // Include files under a build/ directory (compiled Move output).
+    pub include_build: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            max_file_size: Some(1024 * 1024),
+            include: Vec::new(),
+            exclude: DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect(),
+            follow_symlinks: false,
+            include_build: false,
+        }
+    }
+}
+
+// Compiled form of CopyOptions so the regex sets are built once, not per entry.
+struct CompiledFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    max_file_size: Option<u64>,
+    include_build: bool,
+}
+
+impl CompiledFilter {
+    fn compile(options: &CopyOptions) -> Result<Self, regex::Error> {
+        let include = options
+            .include
+            .iter()
+            .map(|p| Regex::new(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let exclude = options
+            .exclude
+            .iter()
+            .map(|p| Regex::new(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            include,
+            exclude,
+            max_file_size: options.max_file_size,
+            include_build: options.include_build,
+        })
+    }
+}
+
+// Walk local_dir and collect the files that survive options, logging a reason
+// for every file it drops by the build, exclude, include or size rules; entries
+// the walk itself fails to read are dropped without a log line. Propagates a
+// malformed include/exclude pattern as an error instead of returning an empty Vec that looks like "all filtered out".
+pub fn get_files(local_dir: &str, options: &CopyOptions) -> Result<Vec<DirEntry>, regex::Error> {
+    let filter = CompiledFilter::compile(options)?;
+
+    Ok(WalkDir::new(local_dir)
+        .follow_links(options.follow_symlinks)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter_map(|entry| {
             let source_path = entry.path();
-            if source_path.to_str().unwrap().contains("/build/") {
+            if !source_path.is_file() {
                 return None;
             }
+            let path_str = source_path.to_str().unwrap();
 
-            if !source_path.is_file() {
+            if !filter.include_build && path_str.contains("/build/") {
+                debug!("get_files: skipping build artifact {}", path_str);
                 return None;
             }
+
             let relative_path = source_path.strip_prefix(local_dir).unwrap();
-            let re = Regex::new(filter_pattern).unwrap();
-            if re.is_match(relative_path.to_str().unwrap()) {
+            let relative_str = relative_path.to_str().unwrap();
+
+            if filter.exclude.iter().any(|re| re.is_match(relative_str)) {
+                warn!("get_files: skipping excluded {}", relative_str);
                 return None;
             }
 
-            let metadata = fs::metadata(source_path).unwrap();
-            let file_size = metadata.len();
-            let file_size_mb = file_size as f64 / (1024.0 * 1024.0);
-            if file_size_mb > 1_f64 {
+            if !filter.include.is_empty()
+                && !filter.include.iter().any(|re| re.is_match(relative_str))
+            {
+                warn!("get_files: skipping non-included {}", relative_str);
                 return None;
             }
+
+            if let Some(max_file_size) = filter.max_file_size {
+                let file_size = fs::metadata(source_path).unwrap().len();
+                if file_size > max_file_size {
+                    warn!(
+                        "get_files: skipping {} ({} bytes exceeds limit of {} bytes)",
+                        relative_str, file_size, max_file_size
+                    );
+                    return None;
+                }
+            }
+
             Some(entry)
         })
-        .collect()
-}
\ No newline at end of file
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Create an isolated temp workspace with the given relative files, each
+    // filled with `size` bytes.
+    fn workspace(files: &[(&str, usize)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("copyopts-{}", generate_random_string(12)));
+        for (rel, size) in files {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, vec![b'x'; *size]).unwrap();
+        }
+        root
+    }
+
+    fn names(root: &str, entries: Vec<DirEntry>) -> Vec<String> {
+        let mut names: Vec<String> = entries
+            .iter()
+            .map(|e| {
+                e.path()
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .replace('\\', "/")
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn build_dir_excluded_by_default_included_on_request() {
+        let root = workspace(&[("src/a.move", 1), ("build/out.mv", 1)]);
+        let root_str = root.to_str().unwrap();
+
+        let kept = names(root_str, get_files(root_str, &CopyOptions::default()).unwrap());
+        assert_eq!(kept, vec!["src/a.move".to_string()]);
+
+        let with_build = CopyOptions {
+            include_build: true,
+            ..CopyOptions::default()
+        };
+        let kept = names(root_str, get_files(root_str, &with_build).unwrap());
+        assert_eq!(kept, vec!["build/out.mv".to_string(), "src/a.move".to_string()]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn oversized_files_are_dropped() {
+        let root = workspace(&[("small.txt", 10), ("big.dat", 2048)]);
+        let root_str = root.to_str().unwrap();
+
+        let options = CopyOptions {
+            max_file_size: Some(1024),
+            exclude: Vec::new(),
+            ..CopyOptions::default()
+        };
+        let kept = names(root_str, get_files(root_str, &options).unwrap());
+        assert_eq!(kept, vec!["small.txt".to_string()]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn include_keeps_only_matching() {
+        let root = workspace(&[("a.move", 1), ("b.txt", 1), ("c.move", 1)]);
+        let root_str = root.to_str().unwrap();
+
+        let options = CopyOptions {
+            include: vec![r"\.move$".to_string()],
+            exclude: Vec::new(),
+            ..CopyOptions::default()
+        };
+        let kept = names(root_str, get_files(root_str, &options).unwrap());
+        assert_eq!(kept, vec!["a.move".to_string(), "c.move".to_string()]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn exclude_takes_precedence_over_include() {
+        let root = workspace(&[("keep.move", 1), ("skip.move", 1)]);
+        let root_str = root.to_str().unwrap();
+
+        let options = CopyOptions {
+            include: vec![r"\.move$".to_string()],
+            exclude: vec![r"^skip\.move$".to_string()],
+            max_file_size: None,
+            follow_symlinks: false,
+            include_build: false,
+        };
+        let kept = names(root_str, get_files(root_str, &options).unwrap());
+        assert_eq!(kept, vec!["keep.move".to_string()]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error_not_an_empty_result() {
+        let root = workspace(&[("a.move", 1)]);
+        let root_str = root.to_str().unwrap();
+
+        let options = CopyOptions {
+            include: vec!["(".to_string()],
+            ..CopyOptions::default()
+        };
+        assert!(get_files(root_str, &options).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}